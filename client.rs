@@ -0,0 +1,358 @@
+//! Off-chain batch-submission client for `execute_payment_distribution`.
+//!
+//! Reads a CSV or JSON file of `(receiver, amount)` rows, splits them into
+//! transaction-sized chunks, and submits the chunks concurrently against the
+//! `payment_validator` program.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use std::sync::Arc;
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Signature, Signer};
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::{Client, Cluster, Program};
+use anyhow::{anyhow, Context, Result};
+use tokio::task::JoinSet;
+
+/// A single `(receiver, amount)` row parsed from the input file.
+#[derive(Clone, Debug)]
+pub struct PayoutRow {
+    pub receiver: Pubkey,
+    pub amount: u64,
+}
+
+/// Wraps a fee payer behind `Box<dyn Signer>` so hardware wallets, on-disk
+/// keypairs and remote signers are all interchangeable from the caller's
+/// point of view.
+pub struct DynSigner(pub Box<dyn Signer>);
+
+impl Signer for DynSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.0.pubkey()
+    }
+
+    fn try_pubkey(&self) -> std::result::Result<Pubkey, anchor_client::solana_sdk::signer::SignerError> {
+        self.0.try_pubkey()
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Signature {
+        self.0.sign_message(message)
+    }
+
+    fn try_sign_message(
+        &self,
+        message: &[u8],
+    ) -> std::result::Result<Signature, anchor_client::solana_sdk::signer::SignerError> {
+        self.0.try_sign_message(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.0.is_interactive()
+    }
+}
+
+/// Solana caps transactions at 1232 bytes. Each receiver costs ~73 bytes:
+/// 32 for its account key in the message, 1 for its account index in the
+/// instruction, plus 32 (`Pubkey`) + 8 (`u64`) duplicated inside the
+/// `execute_payment_distribution` instruction args (`receivers`/`amounts`),
+/// which are serialized in addition to the `remaining_accounts` metas. With
+/// ~217 bytes of fixed overhead (signatures, headers, the instruction
+/// discriminator and its fixed fields), the real ceiling is
+/// `(1232 - 217) / 73` ≈ 13 receivers; this stays a little under that.
+pub const MAX_RECEIVERS_PER_CHUNK: usize = 12;
+
+/// Number of times to retry a chunk whose submission fails before giving up
+/// on it.
+pub const MAX_CHUNK_RETRIES: usize = 3;
+
+/// Parses `path` as CSV (`receiver,amount` per line, optional header) or
+/// JSON (an array of `{"receiver": ..., "amount": ...}` objects), inferred
+/// from the file extension.
+pub fn parse_payout_rows(path: &Path) -> Result<Vec<PayoutRow>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read payouts file {}", path.display()))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => parse_payout_rows_json(&contents),
+        _ => parse_payout_rows_csv(&contents),
+    }
+}
+
+fn parse_payout_rows_csv(contents: &str) -> Result<Vec<PayoutRow>> {
+    let mut rows = Vec::new();
+    let mut seen_data_line = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let is_first_data_line = !seen_data_line;
+        seen_data_line = true;
+
+        let mut fields = line.split(',');
+        let receiver = fields
+            .next()
+            .ok_or_else(|| anyhow!("missing receiver column in line: {line}"))?
+            .trim();
+        let amount = fields
+            .next()
+            .ok_or_else(|| anyhow!("missing amount column in line: {line}"))?
+            .trim();
+
+        let receiver = match Pubkey::from_str(receiver) {
+            Ok(receiver) => receiver,
+            // Only the first non-blank line may be a header; a later row
+            // with an unparseable receiver is a typo'd pubkey, not a header,
+            // and must not be silently dropped from the payroll.
+            Err(_) if is_first_data_line => continue,
+            Err(err) => {
+                return Err(anyhow!(err)).with_context(|| format!("invalid receiver in line: {line}"))
+            }
+        };
+        let amount: u64 = amount
+            .parse()
+            .with_context(|| format!("invalid amount in line: {line}"))?;
+
+        rows.push(PayoutRow { receiver, amount });
+    }
+
+    Ok(rows)
+}
+
+fn parse_payout_rows_json(contents: &str) -> Result<Vec<PayoutRow>> {
+    #[derive(serde::Deserialize)]
+    struct RawRow {
+        receiver: String,
+        amount: u64,
+    }
+
+    let raw_rows: Vec<RawRow> =
+        serde_json::from_str(contents).context("failed to parse payouts JSON")?;
+
+    raw_rows
+        .into_iter()
+        .map(|row| {
+            Ok(PayoutRow {
+                receiver: Pubkey::from_str(&row.receiver)
+                    .with_context(|| format!("invalid receiver pubkey: {}", row.receiver))?,
+                amount: row.amount,
+            })
+        })
+        .collect()
+}
+
+/// Splits `rows` into chunks of at most `MAX_RECEIVERS_PER_CHUNK` so each
+/// resulting transaction stays under Solana's size and compute limits.
+pub fn chunk_payout_rows(rows: &[PayoutRow]) -> Vec<Vec<PayoutRow>> {
+    rows.chunks(MAX_RECEIVERS_PER_CHUNK)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Builds the `execute_payment_distribution` instruction for one chunk,
+/// passing the receiver accounts via `remaining_accounts`.
+pub fn build_chunk_instruction(
+    program: &Program<Arc<DynSigner>>,
+    chunk: &[PayoutRow],
+) -> Result<Instruction> {
+    let total_amount: u64 = chunk.iter().map(|row| row.amount).sum();
+    let receivers: Vec<Pubkey> = chunk.iter().map(|row| row.receiver).collect();
+    let amounts: Vec<u64> = chunk.iter().map(|row| row.amount).collect();
+
+    let remaining_accounts = receivers
+        .iter()
+        .map(|receiver| anchor_client::anchor_lang::prelude::AccountMeta::new(*receiver, false))
+        .collect::<Vec<_>>();
+
+    let request = program
+        .request()
+        .args(payment_validator::instruction::ExecutePaymentDistribution {
+            total_amount,
+            receivers,
+            amounts,
+        })
+        .accounts(payment_validator::accounts::ExecutePayment {
+            sender: program.payer(),
+            system_program: anchor_client::solana_sdk::system_program::ID,
+        });
+
+    let request = remaining_accounts
+        .into_iter()
+        .fold(request, |request, account_meta| request.accounts(account_meta));
+
+    request
+        .instructions()
+        .context("failed to build execute_payment_distribution instruction")?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("no instruction produced for chunk"))
+}
+
+/// Outcome of submitting one chunk.
+pub struct ChunkResult {
+    pub chunk_index: usize,
+    pub signature: Result<Signature>,
+}
+
+/// Submits every chunk's transaction concurrently via a `JoinSet`, retrying
+/// each chunk up to `MAX_CHUNK_RETRIES` times on failure, and returns one
+/// result per chunk in submission order.
+///
+/// Each chunk's blocking RPC work runs on `spawn_blocking` so it can't stall
+/// the Tokio worker threads the other chunks are running on.
+pub async fn submit_chunks_concurrently(
+    cluster: Cluster,
+    fee_payer: Arc<DynSigner>,
+    chunks: Vec<Vec<PayoutRow>>,
+) -> Vec<ChunkResult> {
+    let mut join_set: JoinSet<ChunkResult> = JoinSet::new();
+
+    for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+        let cluster = cluster.clone();
+        let fee_payer = fee_payer.clone();
+
+        join_set.spawn(async move {
+            match tokio::task::spawn_blocking(move || {
+                submit_chunk_blocking(cluster, fee_payer, chunk_index, chunk)
+            })
+            .await
+            {
+                Ok(chunk_result) => chunk_result,
+                Err(join_err) => ChunkResult {
+                    chunk_index,
+                    signature: Err(anyhow!("blocking submission task panicked: {join_err}")),
+                },
+            }
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = join_set.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(join_err) => results.push(ChunkResult {
+                chunk_index: usize::MAX,
+                signature: Err(anyhow!("chunk task panicked: {join_err}")),
+            }),
+        }
+    }
+    results.sort_by_key(|result| result.chunk_index);
+
+    results
+}
+
+/// Builds, signs and sends one chunk's transaction, retrying up to
+/// `MAX_CHUNK_RETRIES` times. Every attempt signs its own transaction (so its
+/// signature is known before sending) and, before retrying a failed attempt,
+/// waits until that attempt's transaction either confirms (reported as
+/// success, no retry) or its blockhash actually expires so it can no longer
+/// land — only then is it safe to sign and send a new transaction for the
+/// chunk without risking a double-pay of every receiver in it.
+fn submit_chunk_blocking(
+    cluster: Cluster,
+    fee_payer: Arc<DynSigner>,
+    chunk_index: usize,
+    chunk: Vec<PayoutRow>,
+) -> ChunkResult {
+    let client = Client::new(cluster, fee_payer.clone());
+    let program = match client.program(payment_validator::ID) {
+        Ok(program) => program,
+        Err(err) => {
+            return ChunkResult {
+                chunk_index,
+                signature: Err(anyhow!(err)),
+            }
+        }
+    };
+    let rpc_client = program.rpc();
+
+    let mut last_err = None;
+    for attempt in 0..=MAX_CHUNK_RETRIES {
+        let instruction = match build_chunk_instruction(&program, &chunk) {
+            Ok(instruction) => instruction,
+            Err(err) => {
+                last_err = Some(err);
+                break; // won't succeed on retry either; nothing changes about the chunk
+            }
+        };
+
+        let recent_blockhash = match rpc_client.get_latest_blockhash() {
+            Ok(blockhash) => blockhash,
+            Err(err) => {
+                last_err = Some(anyhow!(err));
+                continue; // fetching a blockhash never submits anything, so it's always safe to retry
+            }
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&fee_payer.pubkey()),
+            &[&*fee_payer],
+            recent_blockhash,
+        );
+        let signature = transaction.signatures[0];
+
+        match rpc_client.send_and_confirm_transaction(&transaction) {
+            Ok(_) => {
+                return ChunkResult {
+                    chunk_index,
+                    signature: Ok(signature),
+                }
+            }
+            Err(err) => {
+                // The confirmation round-trip can fail even though the
+                // transaction is still live and could land moments later.
+                // Poll until either it confirms (report success, no retry)
+                // or its blockhash actually expires (the transaction can no
+                // longer land, so it's finally safe to sign and send a new
+                // one) — otherwise a retry could double-pay every receiver
+                // in the chunk if both transactions land.
+                loop {
+                    if matches!(
+                        rpc_client.get_signature_status(&signature),
+                        Ok(Some(Ok(())))
+                    ) {
+                        return ChunkResult {
+                            chunk_index,
+                            signature: Ok(signature),
+                        };
+                    }
+
+                    match rpc_client
+                        .is_blockhash_valid(&recent_blockhash, CommitmentConfig::processed())
+                    {
+                        Ok(true) => {
+                            std::thread::sleep(std::time::Duration::from_millis(500));
+                        }
+                        Ok(false) => break,
+                        // Expiry can't be established, so re-sending could
+                        // double-pay: give up on the chunk instead.
+                        Err(check_err) => {
+                            return ChunkResult {
+                                chunk_index,
+                                signature: Err(anyhow!(err)).with_context(|| {
+                                    format!("could not check blockhash expiry: {check_err}")
+                                }),
+                            }
+                        }
+                    }
+                }
+                last_err = Some(anyhow!(err));
+            }
+        }
+
+        if attempt < MAX_CHUNK_RETRIES {
+            std::thread::sleep(std::time::Duration::from_millis(250 * (attempt as u64 + 1)));
+        }
+    }
+
+    ChunkResult {
+        chunk_index,
+        signature: Err(last_err.unwrap_or_else(|| anyhow!("unknown submission failure"))),
+    }
+}
@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
 
 declare_id!("EM7AAngMgQPXizeuwAKaBvci79DhRxJMBYjRVoJWYEH3");
 
@@ -7,14 +12,14 @@ pub mod payment_validator {
     use super::*;
 
     pub fn validate_payment_distribution(
-        ctx: Context<ValidatePayment>, 
-        total_amount: u64, 
-        receivers: Vec<Pubkey>, 
+        ctx: Context<ValidatePayment>,
+        total_amount: u64,
+        receivers: Vec<Pubkey>,
         amounts: Vec<u64>
     ) -> Result<()> {
         // Validate that number of receivers matches number of amounts
         require!(
-            receivers.len() == amounts.len(), 
+            receivers.len() == amounts.len(),
             PaymentError::MismatchedReceiversAndAmounts
         );
 
@@ -23,24 +28,618 @@ pub mod payment_validator {
 
         // Validate that sum matches total amount
         require!(
-            sum == total_amount, 
+            sum == total_amount,
             PaymentError::TotalAmountMismatch
         );
 
         Ok(())
     }
+
+    /// Validates the distribution exactly like `validate_payment_distribution`,
+    /// then actually moves the lamports: one System Program transfer per
+    /// receiver, all within a single instruction so the whole batch lands or
+    /// rolls back together.
+    pub fn execute_payment_distribution(
+        ctx: Context<ExecutePayment>,
+        total_amount: u64,
+        receivers: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            receivers.len() == amounts.len(),
+            PaymentError::MismatchedReceiversAndAmounts
+        );
+
+        let sum: u64 = amounts.iter().sum();
+        require!(sum == total_amount, PaymentError::TotalAmountMismatch);
+
+        require!(
+            ctx.remaining_accounts.len() == receivers.len(),
+            PaymentError::MismatchedReceiverAccounts
+        );
+
+        for (i, receiver_account) in ctx.remaining_accounts.iter().enumerate() {
+            require_keys_eq!(
+                *receiver_account.key,
+                receivers[i],
+                PaymentError::ReceiverAccountMismatch
+            );
+
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.sender.key,
+                    receiver_account.key,
+                    amounts[i],
+                ),
+                &[
+                    ctx.accounts.sender.to_account_info(),
+                    receiver_account.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the `Distribution` PDA for `distribution_id`, recording the
+    /// receiver/amount schedule up front. The PDA starts unfunded, unpaused
+    /// and with no bits claimed; lamports only move once `fund_distribution`
+    /// is called.
+    pub fn init_distribution(
+        ctx: Context<InitDistribution>,
+        distribution_id: u64,
+        total_amount: u64,
+        receivers: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        require!(
+            receivers.len() == amounts.len(),
+            PaymentError::MismatchedReceiversAndAmounts
+        );
+        require!(
+            receivers.len() <= MAX_DISTRIBUTION_RECEIVERS,
+            PaymentError::TooManyReceivers
+        );
+
+        let sum: u64 = amounts.iter().sum();
+        require!(sum == total_amount, PaymentError::TotalAmountMismatch);
+
+        let distribution = &mut ctx.accounts.distribution;
+        distribution.creator = ctx.accounts.creator.key();
+        distribution.distribution_id = distribution_id;
+        distribution.total_amount = total_amount;
+        distribution.receivers = receivers;
+        distribution.amounts = amounts;
+        distribution.funded = false;
+        distribution.paused = false;
+        distribution.claimed_bitmap = 0;
+        distribution.expiry_slot = expiry_slot;
+        distribution.bump = ctx.bumps.distribution;
+
+        Ok(())
+    }
+
+    /// Moves `total_amount` lamports from the creator into the PDA vault so
+    /// receivers can later claim their share.
+    pub fn fund_distribution(ctx: Context<FundDistribution>) -> Result<()> {
+        let distribution = &mut ctx.accounts.distribution;
+        require!(!distribution.paused, PaymentError::DistributionPaused);
+        require!(!distribution.funded, PaymentError::AlreadyFunded);
+
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.creator.key,
+                &distribution.key(),
+                distribution.total_amount,
+            ),
+            &[
+                ctx.accounts.creator.to_account_info(),
+                distribution.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        distribution.funded = true;
+
+        Ok(())
+    }
+
+    /// Lets a single receiver withdraw exactly their allotted amount, once.
+    /// The receiver's bit in `claimed_bitmap` is set so a second claim fails.
+    pub fn claim_distribution(ctx: Context<ClaimDistribution>) -> Result<()> {
+        let receiver_key = ctx.accounts.receiver.key();
+
+        let index = ctx
+            .accounts
+            .distribution
+            .receivers
+            .iter()
+            .position(|r| *r == receiver_key)
+            .ok_or(PaymentError::ReceiverAccountMismatch)?;
+
+        require!(
+            !ctx.accounts.distribution.paused,
+            PaymentError::DistributionPaused
+        );
+        require!(
+            ctx.accounts.distribution.funded,
+            PaymentError::DistributionNotFunded
+        );
+        require!(
+            ctx.accounts.distribution.claimed_bitmap & (1u64 << index) == 0,
+            PaymentError::AlreadyClaimed
+        );
+
+        let amount = ctx.accounts.distribution.amounts[index];
+
+        **ctx
+            .accounts
+            .distribution
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **ctx
+            .accounts
+            .receiver
+            .to_account_info()
+            .try_borrow_mut_lamports()? += amount;
+
+        ctx.accounts.distribution.claimed_bitmap |= 1u64 << index;
+
+        Ok(())
+    }
+
+    /// Reclaims the PDA's rent once every receiver has claimed, or once
+    /// `expiry_slot` has passed, sending any remaining lamports back to the
+    /// creator.
+    pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+        let distribution = &ctx.accounts.distribution;
+        let all_claimed = distribution.receivers.iter().enumerate().all(|(i, _)| {
+            distribution.claimed_bitmap & (1u64 << i) != 0
+        });
+        let expired = Clock::get()?.slot >= distribution.expiry_slot;
+
+        require!(
+            all_claimed || expired,
+            PaymentError::DistributionNotClosable
+        );
+
+        Ok(())
+    }
+
+    /// Admin toggle that, when set, makes `fund_distribution` and
+    /// `claim_distribution` fail with `DistributionPaused`.
+    pub fn set_distribution_paused(
+        ctx: Context<SetDistributionPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        ctx.accounts.distribution.paused = paused;
+        Ok(())
+    }
+
+    /// Variant of `validate_payment_distribution` where payouts are
+    /// expressed as basis-point shares instead of absolute amounts. `shares`
+    /// must sum to exactly 10_000; each payout is floored with u128
+    /// intermediates to avoid overflow, and the integer-division remainder
+    /// ("dust") is assigned to `remainder_index` (defaults to the last
+    /// receiver) so the emitted amounts sum exactly to `total_amount`.
+    pub fn validate_payment_shares(
+        _ctx: Context<ValidatePayment>,
+        total_amount: u64,
+        receivers: Vec<Pubkey>,
+        shares: Vec<u16>,
+        remainder_index: Option<u32>,
+    ) -> Result<Vec<u64>> {
+        require!(
+            receivers.len() == shares.len(),
+            PaymentError::MismatchedReceiversAndAmounts
+        );
+
+        let share_sum: u32 = shares.iter().map(|s| *s as u32).sum();
+        require!(share_sum == BASIS_POINTS_DENOMINATOR, PaymentError::SharesNotFullSum);
+
+        let remainder_index = remainder_index.unwrap_or((receivers.len() - 1) as u32) as usize;
+        require!(
+            remainder_index < receivers.len(),
+            PaymentError::InvalidRemainderIndex
+        );
+
+        let mut payouts: Vec<u64> = shares
+            .iter()
+            .map(|share| {
+                ((total_amount as u128 * *share as u128) / BASIS_POINTS_DENOMINATOR as u128)
+                    as u64
+            })
+            .collect();
+
+        let assigned: u64 = payouts.iter().sum();
+        let dust = total_amount - assigned;
+        payouts[remainder_index] += dust;
+
+        Ok(payouts)
+    }
+
+    /// Registers the committee of `members` pubkeys and the signature
+    /// `threshold` required for `validate_payment_with_quorum` to accept a
+    /// distribution.
+    pub fn init_committee_config(
+        ctx: Context<InitCommitteeConfig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(!members.is_empty(), PaymentError::EmptyCommittee);
+        require!(
+            threshold > 0 && threshold as usize <= members.len(),
+            PaymentError::InvalidThreshold
+        );
+        require!(
+            members.len() <= MAX_COMMITTEE_MEMBERS,
+            PaymentError::TooManyCommitteeMembers
+        );
+
+        let committee_config = &mut ctx.accounts.committee_config;
+        committee_config.authority = ctx.accounts.authority.key();
+        committee_config.members = members;
+        committee_config.threshold = threshold;
+        committee_config.bump = ctx.bumps.committee_config;
+
+        Ok(())
+    }
+
+    /// Accepts a distribution only once at least `threshold` distinct
+    /// committee members have signed the canonical digest of the payload
+    /// (`distribution_id` LE, `expiry_slot` LE, `total_amount` LE, then each
+    /// receiver's bytes, then each amount LE). Binding the digest to
+    /// `distribution_id` and `expiry_slot` stops a committee member's
+    /// signature over one distribution's payload from being replayed
+    /// against a different distribution that happens to carry the same
+    /// receivers/amounts, and `expiry_slot` bounds how long a signed digest
+    /// stays usable at all. The signatures are verified by the Ed25519
+    /// native program in the same transaction; this instruction scans every
+    /// instruction of the transaction for that program's ones via the
+    /// Instructions sysvar and checks the signed message and signer set
+    /// match. Like its sibling `validate_payment_*` instructions, this is
+    /// stateless: it does not record that a given `distribution_id` has
+    /// already been accepted, so a caller holding a still-unexpired,
+    /// already-used quorum of signatures can submit them again. Pair this
+    /// with a stateful caller (e.g. the `Distribution` PDA's `funded` flag)
+    /// if a single distribution must settle at most once.
+    pub fn validate_payment_with_quorum(
+        ctx: Context<ValidatePaymentWithQuorum>,
+        distribution_id: u64,
+        expiry_slot: u64,
+        total_amount: u64,
+        receivers: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            receivers.len() == amounts.len(),
+            PaymentError::MismatchedReceiversAndAmounts
+        );
+        let sum: u64 = amounts.iter().sum();
+        require!(sum == total_amount, PaymentError::TotalAmountMismatch);
+        require!(
+            Clock::get()?.slot <= expiry_slot,
+            PaymentError::QuorumSignatureExpired
+        );
+
+        let mut message =
+            Vec::with_capacity(8 + 8 + 8 + receivers.len() * 32 + amounts.len() * 8);
+        message.extend_from_slice(&distribution_id.to_le_bytes());
+        message.extend_from_slice(&expiry_slot.to_le_bytes());
+        message.extend_from_slice(&total_amount.to_le_bytes());
+        for receiver in receivers.iter() {
+            message.extend_from_slice(receiver.as_ref());
+        }
+        for amount in amounts.iter() {
+            message.extend_from_slice(&amount.to_le_bytes());
+        }
+        let digest = hashv(&[&message]).to_bytes();
+
+        let committee_config = &ctx.accounts.committee_config;
+        let instructions_sysvar = &ctx.accounts.instructions_sysvar;
+
+        let mut unique_signers: Vec<Pubkey> = Vec::new();
+        let mut index: usize = 0;
+        while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+            if instruction.program_id == ed25519_program::ID {
+                for (pubkey, signed_message) in
+                    parse_ed25519_instruction(&instruction.data, index as u16)?
+                {
+                    if signed_message == digest
+                        && committee_config.members.contains(&pubkey)
+                        && !unique_signers.contains(&pubkey)
+                    {
+                        unique_signers.push(pubkey);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        require!(
+            unique_signers.len() as u8 >= committee_config.threshold,
+            PaymentError::QuorumNotMet
+        );
+
+        Ok(())
+    }
 }
 
+pub const BASIS_POINTS_DENOMINATOR: u32 = 10_000;
+pub const MAX_COMMITTEE_MEMBERS: usize = 32;
+
+/// Sentinel used by the Ed25519 native program's offsets struct to mean
+/// "this instruction" instead of naming an explicit instruction index.
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Parses a native Ed25519 program instruction's data into
+/// `(signer_pubkey, signed_message)` pairs, per the layout documented at
+/// https://docs.rs/solana-program/latest/solana_program/ed25519_program/.
+///
+/// `current_index` is this instruction's own index in the transaction.
+/// Every entry's `signature_instruction_index`, `public_key_instruction_index`
+/// and `message_instruction_index` must each equal `current_index` (or the
+/// `u16::MAX` "current instruction" sentinel); otherwise the entry is
+/// pointing its pubkey/message/signature at a *different* instruction the
+/// caller controls, which would let an attacker splice a real committee
+/// pubkey and the target digest into this instruction's data while having
+/// the native program actually verify an attacker-owned signature elsewhere.
+/// Such an entry is rejected rather than trusted.
+fn parse_ed25519_instruction(data: &[u8], current_index: u16) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let num_signatures = data[0] as usize;
+    let mut entries = Vec::with_capacity(num_signatures);
+    let mut offset = 2usize; // 1 byte count + 1 byte padding
+
+    for _ in 0..num_signatures {
+        require!(
+            data.len() >= offset + 14,
+            PaymentError::MalformedEd25519Instruction
+        );
+
+        let signature_instruction_index = u16::from_le_bytes([data[offset + 2], data[offset + 3]]);
+        let public_key_offset = u16::from_le_bytes([data[offset + 4], data[offset + 5]]) as usize;
+        let public_key_instruction_index = u16::from_le_bytes([data[offset + 6], data[offset + 7]]);
+        let message_data_offset =
+            u16::from_le_bytes([data[offset + 8], data[offset + 9]]) as usize;
+        let message_data_size =
+            u16::from_le_bytes([data[offset + 10], data[offset + 11]]) as usize;
+        let message_instruction_index =
+            u16::from_le_bytes([data[offset + 12], data[offset + 13]]);
+
+        let points_at_this_instruction = |instruction_index: u16| {
+            instruction_index == current_index || instruction_index == ED25519_CURRENT_INSTRUCTION
+        };
+        require!(
+            points_at_this_instruction(signature_instruction_index)
+                && points_at_this_instruction(public_key_instruction_index)
+                && points_at_this_instruction(message_instruction_index),
+            PaymentError::Ed25519InstructionIndexMismatch
+        );
+
+        require!(
+            data.len() >= public_key_offset + 32
+                && data.len() >= message_data_offset + message_data_size,
+            PaymentError::MalformedEd25519Instruction
+        );
+
+        let pubkey = Pubkey::new_from_array(
+            data[public_key_offset..public_key_offset + 32]
+                .try_into()
+                .unwrap(),
+        );
+        let signed_message =
+            data[message_data_offset..message_data_offset + message_data_size].to_vec();
+
+        entries.push((pubkey, signed_message));
+        offset += 14;
+    }
+
+    Ok(entries)
+}
+
+pub const MAX_DISTRIBUTION_RECEIVERS: usize = 32;
+
 #[derive(Accounts)]
 pub struct ValidatePayment<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ExecutePayment<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    // Receiver accounts are passed via `ctx.remaining_accounts`, one per
+    // entry in `receivers`, in the same order.
+}
+
+#[account]
+pub struct Distribution {
+    pub creator: Pubkey,
+    pub distribution_id: u64,
+    pub total_amount: u64,
+    pub receivers: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub funded: bool,
+    pub paused: bool,
+    pub claimed_bitmap: u64,
+    pub expiry_slot: u64,
+    pub bump: u8,
+}
+
+impl Distribution {
+    // discriminator + creator + id + total_amount
+    // + vec lens/elements (bounded by MAX_DISTRIBUTION_RECEIVERS)
+    // + funded + paused + claimed_bitmap + expiry_slot + bump
+    pub const MAX_SIZE: usize = 8
+        + 32
+        + 8
+        + 8
+        + (4 + MAX_DISTRIBUTION_RECEIVERS * 32)
+        + (4 + MAX_DISTRIBUTION_RECEIVERS * 8)
+        + 1
+        + 1
+        + 8
+        + 8
+        + 1;
+}
+
+#[derive(Accounts)]
+#[instruction(distribution_id: u64)]
+pub struct InitDistribution<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = Distribution::MAX_SIZE,
+        seeds = [b"distribution", creator.key().as_ref(), &distribution_id.to_le_bytes()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundDistribution<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"distribution", creator.key().as_ref(), &distribution.distribution_id.to_le_bytes()],
+        bump = distribution.bump,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution.creator.as_ref(), &distribution.distribution_id.to_le_bytes()],
+        bump = distribution.bump,
+    )]
+    pub distribution: Account<'info, Distribution>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDistribution<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator,
+        seeds = [b"distribution", creator.key().as_ref(), &distribution.distribution_id.to_le_bytes()],
+        bump = distribution.bump,
+    )]
+    pub distribution: Account<'info, Distribution>,
+}
+
+#[derive(Accounts)]
+pub struct SetDistributionPaused<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"distribution", creator.key().as_ref(), &distribution.distribution_id.to_le_bytes()],
+        bump = distribution.bump,
+    )]
+    pub distribution: Account<'info, Distribution>,
+}
+
+#[account]
+pub struct CommitteeConfig {
+    pub authority: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl CommitteeConfig {
+    // discriminator + authority + vec len/elements (bounded by
+    // MAX_COMMITTEE_MEMBERS) + threshold + bump
+    pub const MAX_SIZE: usize = 8 + 32 + (4 + MAX_COMMITTEE_MEMBERS * 32) + 1 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitCommitteeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = CommitteeConfig::MAX_SIZE,
+        seeds = [b"committee", authority.key().as_ref()],
+        bump
+    )]
+    pub committee_config: Account<'info, CommitteeConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ValidatePaymentWithQuorum<'info> {
+    pub sender: Signer<'info>,
+    #[account(
+        seeds = [b"committee", committee_config.authority.as_ref()],
+        bump = committee_config.bump,
+    )]
+    pub committee_config: Account<'info, CommitteeConfig>,
+    /// CHECK: constrained to the Instructions sysvar address below; only read via `load_instruction_at_checked`.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
 #[error_code]
 pub enum PaymentError {
     #[msg("Number of receivers does not match number of amounts")]
     MismatchedReceiversAndAmounts,
     #[msg("Total amount does not match sum of individual amounts")]
     TotalAmountMismatch,
+    #[msg("Number of remaining accounts does not match number of receivers")]
+    MismatchedReceiverAccounts,
+    #[msg("Receiver account key does not match the expected receiver")]
+    ReceiverAccountMismatch,
+    #[msg("Too many receivers for a single distribution")]
+    TooManyReceivers,
+    #[msg("Distribution has already been funded")]
+    AlreadyFunded,
+    #[msg("Distribution has not been funded yet")]
+    DistributionNotFunded,
+    #[msg("Receiver has already claimed their share")]
+    AlreadyClaimed,
+    #[msg("Distribution is paused")]
+    DistributionPaused,
+    #[msg("Distribution cannot be closed until all receivers have claimed or it has expired")]
+    DistributionNotClosable,
+    #[msg("Shares must sum to exactly 10,000 basis points")]
+    SharesNotFullSum,
+    #[msg("Remainder index is out of bounds for the receivers list")]
+    InvalidRemainderIndex,
+    #[msg("Committee must have at least one member")]
+    EmptyCommittee,
+    #[msg("Threshold must be between 1 and the number of committee members")]
+    InvalidThreshold,
+    #[msg("Too many members for a single committee")]
+    TooManyCommitteeMembers,
+    #[msg("Ed25519 instruction data is malformed")]
+    MalformedEd25519Instruction,
+    #[msg("Ed25519 signature/pubkey/message does not point at this instruction")]
+    Ed25519InstructionIndexMismatch,
+    #[msg("Not enough committee members signed the distribution")]
+    QuorumNotMet,
+    #[msg("Quorum signature digest has expired")]
+    QuorumSignatureExpired,
 }
\ No newline at end of file